@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+use crate::storage::AssetFilters;
+use crate::StorageState;
+
+/// Extensions this importer recognizes, mapped to the `asset_type` stored
+/// on the resulting row. Anything else is ignored: it is neither imported
+/// nor counted in the summary.
+const SUPPORTED_EXTENSIONS: &[(&str, &str)] = &[
+    ("png", "texture"),
+    ("jpg", "texture"),
+    ("jpeg", "texture"),
+    ("gif", "texture"),
+    ("webp", "texture"),
+    ("wav", "audio"),
+    ("mp3", "audio"),
+    ("ogg", "audio"),
+    ("json", "data"),
+];
+
+fn asset_type_for_extension(ext: &str) -> Option<&'static str> {
+    SUPPORTED_EXTENSIONS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(ext))
+        .map(|(_, asset_type)| *asset_type)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportSummary {
+    pub imported: usize,
+    pub skipped: usize,
+    pub errored: usize,
+    pub errors: Vec<String>,
+}
+
+struct ScannedFile {
+    path: PathBuf,
+    asset_type: &'static str,
+    size: u64,
+    hash: String,
+}
+
+fn hash_file(path: &Path) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Runs on the blocking pool: stats and hashes one candidate file, or
+/// returns `None` if its extension isn't a supported asset type.
+fn scan_file(path: PathBuf) -> Result<Option<ScannedFile>, String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let Some(asset_type) = asset_type_for_extension(&ext) else {
+        return Ok(None);
+    };
+
+    let size = std::fs::metadata(&path)
+        .map_err(|e| format!("Failed to stat {}: {}", path.display(), e))?
+        .len();
+    let hash = hash_file(&path)?;
+
+    Ok(Some(ScannedFile { path, asset_type, size, hash }))
+}
+
+/// Existing `file_path` and content-hash values already in the store, used
+/// to skip files that have already been imported.
+async fn existing_fingerprints(storage: &StorageState) -> Result<(HashSet<String>, HashSet<String>), String> {
+    let assets = storage.0.get_assets(AssetFilters::default()).await?;
+
+    let mut paths = HashSet::new();
+    let mut hashes = HashSet::new();
+    for asset in assets {
+        if let Some(path) = asset.get("file_path").and_then(|v| v.as_str()) {
+            paths.insert(path.to_string());
+        }
+        if let Some(hash) = asset
+            .get("metadata")
+            .and_then(|m| m.get("content_hash"))
+            .and_then(|h| h.as_str())
+        {
+            hashes.insert(hash.to_string());
+        }
+    }
+
+    Ok((paths, hashes))
+}
+
+/// Recursively imports supported asset files under `root` into the
+/// `assets` table. The directory walk and the per-file hashing both run
+/// on the blocking pool so a large library doesn't stall the UI, while
+/// already-known files (by path or content hash) are skipped.
+pub async fn import_directory(
+    app_handle: AppHandle,
+    storage: State<'_, StorageState>,
+    root: String,
+) -> Result<ImportSummary, String> {
+    let root_path = PathBuf::from(root);
+
+    let paths: Vec<PathBuf> = tokio::task::spawn_blocking(move || {
+        walkdir::WalkDir::new(&root_path)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Directory walk failed: {}", e))?;
+
+    let total = paths.len();
+
+    let (existing_paths, existing_hashes) = existing_fingerprints(&storage).await?;
+
+    let semaphore = Arc::new(Semaphore::new(
+        std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4),
+    ));
+
+    let mut scan_tasks = Vec::with_capacity(total);
+    for path in paths {
+        let semaphore = semaphore.clone();
+        scan_tasks.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.map_err(|e| format!("Import worker semaphore closed: {}", e))?;
+            tokio::task::spawn_blocking(move || scan_file(path))
+                .await
+                .map_err(|e| format!("Scan task failed: {}", e))?
+        }));
+    }
+
+    let mut summary = ImportSummary { imported: 0, skipped: 0, errored: 0, errors: Vec::new() };
+    let mut processed = 0usize;
+    // Content hashes already queued for insert in this run, so duplicate
+    // files found at different paths during the same walk are only
+    // imported once, not just duplicates of what was already on disk.
+    let mut seen_hashes: HashSet<String> = HashSet::new();
+    let mut to_insert = Vec::new();
+
+    for task in scan_tasks {
+        processed += 1;
+
+        match task.await {
+            Ok(Ok(Some(file))) => {
+                let path_str = file.path.to_string_lossy().to_string();
+                if existing_paths.contains(&path_str)
+                    || existing_hashes.contains(&file.hash)
+                    || !seen_hashes.insert(file.hash.clone())
+                {
+                    summary.skipped += 1;
+                } else {
+                    let name = file
+                        .path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("unknown")
+                        .to_string();
+
+                    to_insert.push(serde_json::json!({
+                        "id": Uuid::new_v4().to_string(),
+                        "asset_type": file.asset_type,
+                        "name": name,
+                        "file_path": path_str,
+                        "file_size": file.size,
+                        "metadata": {
+                            "content_hash": file.hash,
+                            "imported_at": Utc::now().to_rfc3339()
+                        }
+                    }));
+                }
+            }
+            Ok(Ok(None)) => {}
+            Ok(Err(e)) => {
+                summary.errored += 1;
+                summary.errors.push(e);
+            }
+            Err(join_err) => {
+                summary.errored += 1;
+                summary.errors.push(format!("Scan task panicked: {}", join_err));
+            }
+        }
+
+        let _ = app_handle.emit(
+            "import://progress",
+            serde_json::json!({ "processed": processed, "total": total }),
+        );
+    }
+
+    // Bulk-insert everything that survived dedup in one go, rather than one
+    // pooled connection/transaction per file.
+    for result in storage.0.save_assets_batch(to_insert).await {
+        match result {
+            Ok(_) => summary.imported += 1,
+            Err(e) => {
+                summary.errored += 1;
+                summary.errors.push(e);
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_storage::MemoryStorage;
+
+    #[tokio::test]
+    async fn existing_fingerprints_collects_paths_and_content_hashes() {
+        let storage = StorageState(Arc::new(MemoryStorage::new()));
+
+        storage.0.save_asset(serde_json::json!({
+            "asset_type": "texture",
+            "name": "wall.png",
+            "file_path": "/library/wall.png",
+            "metadata": { "content_hash": "abc123" }
+        })).await.expect("save_asset");
+
+        let (paths, hashes) = existing_fingerprints(&storage).await.expect("existing_fingerprints");
+
+        assert!(paths.contains("/library/wall.png"));
+        assert!(hashes.contains("abc123"));
+    }
+}
@@ -1,62 +1,47 @@
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 use chrono::Utc;
 use std::fs;
-use rusqlite::{Connection, params};
+use rusqlite::params;
 use uuid::Uuid;
 use tauri_plugin_dialog::DialogExt;
-use std::sync::Mutex;
 use tauri::State;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::Semaphore;
 
-// Database state management
-struct DbState(Mutex<Connection>);
+mod import;
+mod memory_storage;
+mod migrations;
+mod sqlite_storage;
+mod storage;
 
-fn init_database(app_handle: &tauri::AppHandle) -> Result<Connection, String> {
+use sqlite_storage::{DbPool, SqliteStorage};
+use storage::{AssetFilters, Storage};
+
+// Database state management. The storage trait object is shared behind an
+// `Arc` (rather than boxed) so batch workers can clone a handle into each
+// spawned task.
+struct StorageState(Arc<dyn Storage>);
+
+// Raw pool, kept alongside `StorageState` for commands the `Storage` trait
+// doesn't cover (collections).
+struct DbState(DbPool);
+
+fn init_database(app_handle: &tauri::AppHandle) -> Result<(Arc<dyn Storage>, DbPool), String> {
     let app_dir = app_handle.path().app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-    
+
     fs::create_dir_all(&app_dir)
         .map_err(|e| format!("Failed to create app directory: {}", e))?;
-    
+
     let db_path = app_dir.join("tpt_assets.db");
-    let conn = Connection::open(&db_path)
-        .map_err(|e| format!("Failed to open database: {}", e))?;
-    
-    // Create tables
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS assets (
-            id TEXT PRIMARY KEY,
-            asset_type TEXT NOT NULL,
-            name TEXT NOT NULL,
-            config TEXT,
-            metadata TEXT,
-            file_path TEXT,
-            file_size INTEGER,
-            quality_score INTEGER,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        )",
-        [],
-    ).map_err(|e| format!("Failed to create assets table: {}", e))?;
-    
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS settings (
-            key TEXT PRIMARY KEY,
-            value TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        )",
-        [],
-    ).map_err(|e| format!("Failed to create settings table: {}", e))?;
-    
-    Ok(conn)
-}
 
-#[derive(Debug, Serialize, Deserialize)]
-struct AssetFilters {
-    r#type: Option<String>,
-    search: Option<String>,
-    limit: Option<u32>,
+    let sqlite_storage = SqliteStorage::open(&db_path)?;
+    let pool = sqlite_storage.pool();
+
+    Ok((Arc::new(sqlite_storage), pool))
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -86,8 +71,9 @@ pub fn run() {
       }
       
       // Initialize database
-      let conn = init_database(&app.handle())?;
-      app.manage(DbState(Mutex::new(conn)));
+      let (storage, pool) = init_database(&app.handle())?;
+      app.manage(StorageState(storage));
+      app.manage(DbState(pool));
       
       Ok(())
     })
@@ -101,9 +87,12 @@ pub fn run() {
       db_delete_asset,
       db_get_setting,
       db_save_setting,
+      db_create_collection,
+      db_assign_asset,
       fs_save_file,
       fs_read_file,
       fs_ensure_dir,
+      fs_import_directory,
       dialog_open_directory,
       dialog_save_file,
       generate_asset,
@@ -114,195 +103,102 @@ pub fn run() {
 }
 
 #[tauri::command]
-// Helper function to create a row mapper
-fn create_asset_row_mapper() -> impl Fn(&rusqlite::Row) -> rusqlite::Result<serde_json::Value> {
-    |row| {
-        let config_str: Option<String> = row.get(3)?;
-        let metadata_str: Option<String> = row.get(4)?;
-        
-        let config: Option<serde_json::Value> = config_str
-            .and_then(|s| serde_json::from_str(&s).ok());
-        let metadata: Option<serde_json::Value> = metadata_str
-            .and_then(|s| serde_json::from_str(&s).ok());
-        
-        Ok(serde_json::json!({
-            "id": row.get::<_, String>(0)?,
-            "asset_type": row.get::<_, String>(1)?,
-            "name": row.get::<_, String>(2)?,
-            "config": config,
-            "metadata": metadata,
-            "file_path": row.get::<_, Option<String>>(5)?,
-            "file_size": row.get::<_, Option<u64>>(6)?,
-            "quality_score": row.get::<_, Option<u32>>(7)?,
-            "created_at": row.get::<_, String>(8)?,
-            "updated_at": row.get::<_, String>(9)?
-        }))
-    }
-}
-
 async fn db_get_assets(
-    db_state: State<'_, DbState>,
+    storage: State<'_, StorageState>,
     filters: AssetFilters
 ) -> Result<Vec<serde_json::Value>, String> {
-    let conn = db_state.0.lock().map_err(|e| format!("Database lock error: {}", e))?;
-    
-    let mut query = "SELECT id, asset_type, name, config, metadata, file_path, file_size, quality_score, created_at, updated_at FROM assets".to_string();
-    let mut conditions = Vec::new();
-    
-    // Build query conditions
-    if filters.r#type.is_some() {
-        conditions.push("asset_type = ?");
-    }
-    
-    if filters.search.is_some() {
-        conditions.push("name LIKE ?");
-    }
-    
-    if !conditions.is_empty() {
-        query.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
-    }
-    
-    query.push_str(" ORDER BY updated_at DESC");
-    
-    if let Some(limit) = filters.limit {
-        query.push_str(&format!(" LIMIT {}", limit));
-    }
-    
-    let mut stmt = conn.prepare(&query)
-        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
-    
-    // Execute query with proper parameter binding
-    let rows = if let (Some(asset_type), Some(search)) = (&filters.r#type, &filters.search) {
-        let search_pattern = format!("%{}%", search);
-        stmt.query_map(params![asset_type, search_pattern], create_asset_row_mapper())
-            .map_err(|e| format!("Query execution error: {}", e))?
-    } else if let Some(asset_type) = &filters.r#type {
-        stmt.query_map(params![asset_type], create_asset_row_mapper())
-            .map_err(|e| format!("Query execution error: {}", e))?
-    } else if let Some(search) = &filters.search {
-        let search_pattern = format!("%{}%", search);
-        stmt.query_map(params![search_pattern], create_asset_row_mapper())
-            .map_err(|e| format!("Query execution error: {}", e))?
-    } else {
-        stmt.query_map([], create_asset_row_mapper())
-            .map_err(|e| format!("Query execution error: {}", e))?
-    };
-    
-    let mut assets = Vec::new();
-    for asset_result in rows {
-        assets.push(asset_result.map_err(|e| format!("Row parsing error: {}", e))?);
-    }
-    
-    Ok(assets)
+    storage.0.get_assets(filters).await
 }
 
 #[tauri::command]
 async fn db_save_asset(
-    db_state: State<'_, DbState>,
+    storage: State<'_, StorageState>,
     asset: serde_json::Value
 ) -> Result<String, String> {
-    let conn = db_state.0.lock().map_err(|e| format!("Database lock error: {}", e))?;
-    
-    let default_id = Uuid::new_v4().to_string();
-    let id = asset.get("id")
-        .and_then(|v| v.as_str())
-        .unwrap_or(&default_id);
-    
-    let asset_type = asset.get("asset_type")
-        .and_then(|v| v.as_str())
-        .ok_or("Missing asset_type")?;
-    
-    let name = asset.get("name")
-        .and_then(|v| v.as_str())
-        .ok_or("Missing name")?;
-    
-    let config_str = asset.get("config")
-        .map(|v| serde_json::to_string(v).unwrap_or_default());
-    
-    let metadata_str = asset.get("metadata")
-        .map(|v| serde_json::to_string(v).unwrap_or_default());
-    
-    let file_path = asset.get("file_path")
-        .and_then(|v| v.as_str());
-    
-    let file_size = asset.get("file_size")
-        .and_then(|v| v.as_u64());
-    
-    let quality_score = asset.get("quality_score")
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u32);
-    
-    let now = Utc::now().to_rfc3339();
-    let created_at = asset.get("created_at")
-        .and_then(|v| v.as_str())
-        .unwrap_or(&now);
-    
-    conn.execute(
-        "INSERT OR REPLACE INTO assets 
-         (id, asset_type, name, config, metadata, file_path, file_size, quality_score, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-        params![id, asset_type, name, config_str, metadata_str, file_path, file_size, quality_score, created_at, now],
-    ).map_err(|e| format!("Failed to save asset: {}", e))?;
-    
-    Ok(id.to_string())
+    storage.0.save_asset(asset).await
 }
 
 #[tauri::command]
 async fn db_delete_asset(
-    db_state: State<'_, DbState>,
+    storage: State<'_, StorageState>,
     asset_id: String
 ) -> Result<String, String> {
-    let conn = db_state.0.lock().map_err(|e| format!("Database lock error: {}", e))?;
-    
-    let rows_affected = conn.execute(
-        "DELETE FROM assets WHERE id = ?1",
-        params![asset_id],
-    ).map_err(|e| format!("Failed to delete asset: {}", e))?;
-    
-    if rows_affected == 0 {
-        return Err("Asset not found".to_string());
-    }
-    
+    storage.0.delete_asset(&asset_id).await?;
     Ok("Asset deleted successfully".to_string())
 }
 
 #[tauri::command]
 async fn db_get_setting(
-    db_state: State<'_, DbState>,
+    storage: State<'_, StorageState>,
     key: String
 ) -> Result<Option<String>, String> {
-    let conn = db_state.0.lock().map_err(|e| format!("Database lock error: {}", e))?;
-    
-    let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?1")
-        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
-    
-    let result = stmt.query_row(params![key], |row| {
-        Ok(row.get::<_, String>(0)?)
-    });
-    
-    match result {
-        Ok(value) => Ok(Some(value)),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Database error: {}", e))
-    }
+    storage.0.get_setting(&key).await
 }
 
 #[tauri::command]
 async fn db_save_setting(
-    db_state: State<'_, DbState>,
+    storage: State<'_, StorageState>,
     key: String,
     value: String
 ) -> Result<String, String> {
-    let conn = db_state.0.lock().map_err(|e| format!("Database lock error: {}", e))?;
-    
+    storage.0.save_setting(&key, &value).await?;
+    Ok("Setting saved successfully".to_string())
+}
+
+#[tauri::command]
+async fn db_create_collection(
+    db_state: State<'_, DbState>,
+    name: String,
+    parent_id: Option<String>
+) -> Result<String, String> {
+    let conn = db_state.0.get().map_err(|e| format!("Failed to check out database connection: {}", e))?;
+
+    let id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
-    
+
     conn.execute(
-        "INSERT OR REPLACE INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)",
-        params![key, value, now],
-    ).map_err(|e| format!("Failed to save setting: {}", e))?;
-    
-    Ok("Setting saved successfully".to_string())
+        "INSERT INTO collections (id, name, parent_id, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![id, name, parent_id, now],
+    ).map_err(|e| format!("Failed to create collection: {}", e))?;
+
+    Ok(id)
+}
+
+#[tauri::command]
+async fn db_assign_asset(
+    db_state: State<'_, DbState>,
+    asset_id: String,
+    collection_id: String
+) -> Result<String, String> {
+    let conn = db_state.0.get().map_err(|e| format!("Failed to check out database connection: {}", e))?;
+
+    let asset_exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM assets WHERE id = ?1)",
+        params![asset_id],
+        |row| row.get(0),
+    ).map_err(|e| format!("Failed to look up asset: {}", e))?;
+    if !asset_exists {
+        return Err(format!("Asset not found: {}", asset_id));
+    }
+
+    let collection_exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM collections WHERE id = ?1)",
+        params![collection_id],
+        |row| row.get(0),
+    ).map_err(|e| format!("Failed to look up collection: {}", e))?;
+    if !collection_exists {
+        return Err(format!("Collection not found: {}", collection_id));
+    }
+
+    // `OR IGNORE` here only covers re-assigning an asset that's already in
+    // the collection (a duplicate primary key); a missing asset or
+    // collection has already been reported by the existence checks above.
+    conn.execute(
+        "INSERT OR IGNORE INTO asset_collections (asset_id, collection_id) VALUES (?1, ?2)",
+        params![asset_id, collection_id],
+    ).map_err(|e| format!("Failed to assign asset to collection: {}", e))?;
+
+    Ok("Asset assigned to collection".to_string())
 }
 
 #[tauri::command]
@@ -343,6 +239,15 @@ async fn fs_ensure_dir(path: String) -> Result<String, String> {
     Ok(format!("Directory ensured: {}", path))
 }
 
+#[tauri::command]
+async fn fs_import_directory(
+    app_handle: tauri::AppHandle,
+    storage: State<'_, StorageState>,
+    path: String
+) -> Result<import::ImportSummary, String> {
+    import::import_directory(app_handle, storage, path).await
+}
+
 #[tauri::command]
 async fn dialog_open_directory(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
     use std::sync::mpsc;
@@ -422,9 +327,137 @@ async fn generate_asset(asset_type: String, config: serde_json::Value) -> Result
     }))
 }
 
+/// Reads the `batch_parallelism` setting (a user-configurable cap on
+/// in-flight generation tasks), falling back to the number of available
+/// CPU cores when it's unset or not a valid positive integer.
+async fn batch_parallelism(storage: &dyn Storage) -> usize {
+    let default_parallelism = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+
+    storage.get_setting("batch_parallelism").await
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(default_parallelism)
+}
+
+/// Runs `generate_asset` for one batch request and persists the result,
+/// mapping the generator's `{type, config, data, metadata}` blob into the
+/// `asset_type`/`name` shape `Storage::save_asset` requires. `index` is only
+/// used to derive a fallback name when the request's config doesn't supply
+/// one.
+async fn generate_and_save(
+    storage: &dyn Storage,
+    index: usize,
+    request: serde_json::Value
+) -> Result<(String, serde_json::Value), String> {
+    let asset_type = request.get("asset_type")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing asset_type")?
+        .to_string();
+    let config = request.get("config").cloned().unwrap_or(serde_json::Value::Null);
+
+    let mut generated = generate_asset(asset_type.clone(), config).await?;
+
+    let name = generated.get("config")
+        .and_then(|c| c.get("name"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| format!("{} {}", asset_type, index + 1));
+
+    if let Some(obj) = generated.as_object_mut() {
+        obj.insert("asset_type".to_string(), serde_json::Value::String(asset_type));
+        obj.insert("name".to_string(), serde_json::Value::String(name));
+    }
+
+    let id = storage.save_asset(generated.clone()).await?;
+    Ok((id, generated))
+}
+
 #[tauri::command]
-async fn generate_batch(_assets: Vec<serde_json::Value>) -> Result<Vec<serde_json::Value>, String> {
-    // This would generate multiple assets
-    // For now, returning an empty vector as a placeholder
-    Ok(vec![])
+async fn generate_batch(
+    app_handle: tauri::AppHandle,
+    storage: State<'_, StorageState>,
+    assets: Vec<serde_json::Value>
+) -> Result<serde_json::Value, String> {
+    let total = assets.len();
+
+    let parallelism = batch_parallelism(storage.0.as_ref()).await;
+
+    let semaphore = Arc::new(Semaphore::new(parallelism));
+    let completed = Arc::new(AtomicUsize::new(0));
+
+    let mut tasks = Vec::with_capacity(total);
+
+    for (index, request) in assets.into_iter().enumerate() {
+        let semaphore = semaphore.clone();
+        let completed = completed.clone();
+        let app_handle = app_handle.clone();
+        let storage = storage.0.clone();
+
+        tasks.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await
+                .map_err(|e| format!("Batch worker semaphore closed: {}", e))?;
+
+            let (id, generated) = generate_and_save(storage.as_ref(), index, request).await?;
+
+            let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
+            let _ = app_handle.emit("batch://progress", serde_json::json!({
+                "completed": done,
+                "total": total
+            }));
+            let _ = app_handle.emit("batch://item-done", serde_json::json!({ "id": id }));
+
+            Ok::<serde_json::Value, String>(serde_json::json!({ "index": index, "id": id, "asset": generated }))
+        }));
+    }
+
+    let mut results = Vec::with_capacity(total);
+    let mut errors = Vec::new();
+
+    for task in tasks {
+        match task.await {
+            Ok(Ok(value)) => results.push(value),
+            Ok(Err(e)) => errors.push(e),
+            Err(join_err) => errors.push(format!("Batch worker task failed: {}", join_err)),
+        }
+    }
+
+    Ok(serde_json::json!({ "results": results, "errors": errors }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use memory_storage::MemoryStorage;
+
+    #[tokio::test]
+    async fn generate_and_save_persists_the_generated_asset() {
+        let storage = MemoryStorage::new();
+        let request = serde_json::json!({ "asset_type": "texture", "config": { "width": 16 } });
+
+        let (id, generated) = generate_and_save(&storage, 0, request).await.expect("generate_and_save");
+
+        assert_eq!(generated.get("asset_type").and_then(|v| v.as_str()), Some("texture"));
+        assert_eq!(generated.get("name").and_then(|v| v.as_str()), Some("texture 1"));
+
+        let saved = storage.get_assets(AssetFilters::default()).await.expect("get_assets");
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].get("id").and_then(|v| v.as_str()), Some(id.as_str()));
+    }
+
+    #[tokio::test]
+    async fn generate_and_save_prefers_a_name_from_config() {
+        let storage = MemoryStorage::new();
+        let request = serde_json::json!({
+            "asset_type": "audio",
+            "config": { "name": "footstep-01" }
+        });
+
+        let (_, generated) = generate_and_save(&storage, 3, request).await.expect("generate_and_save");
+
+        assert_eq!(generated.get("name").and_then(|v| v.as_str()), Some("footstep-01"));
+    }
 }
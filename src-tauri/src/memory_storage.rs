@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::storage::{AssetFilters, Storage};
+
+/// `HashMap`-backed `Storage` used in tests so the batch worker, import
+/// scanner, and search layers can be exercised deterministically without a
+/// real database file. Search here is a plain substring match rather than
+/// BM25 ranking, and `collection_id` scoping isn't supported.
+#[derive(Default)]
+pub struct MemoryStorage {
+    assets: Mutex<HashMap<String, serde_json::Value>>,
+    settings: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    async fn get_assets(&self, filters: AssetFilters) -> Result<Vec<serde_json::Value>, String> {
+        let assets = self.assets.lock().map_err(|e| format!("Asset store lock poisoned: {}", e))?;
+
+        let search = filters.search.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_lowercase);
+
+        let mut matching: Vec<serde_json::Value> = assets
+            .values()
+            .filter(|asset| {
+                filters.r#type.as_deref().map_or(true, |t| {
+                    asset.get("asset_type").and_then(|v| v.as_str()) == Some(t)
+                })
+            })
+            .filter(|asset| match &search {
+                None => true,
+                Some(query) => asset
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .map(|name| name.to_lowercase().contains(query))
+                    .unwrap_or(false),
+            })
+            .cloned()
+            .collect();
+
+        matching.sort_by(|a, b| {
+            let a_updated = a.get("updated_at").and_then(|v| v.as_str()).unwrap_or_default();
+            let b_updated = b.get("updated_at").and_then(|v| v.as_str()).unwrap_or_default();
+            b_updated.cmp(a_updated)
+        });
+
+        if let Some(limit) = filters.limit {
+            matching.truncate(limit as usize);
+        }
+
+        Ok(matching)
+    }
+
+    async fn save_asset(&self, mut asset: serde_json::Value) -> Result<String, String> {
+        let mut assets = self.assets.lock().map_err(|e| format!("Asset store lock poisoned: {}", e))?;
+
+        let default_id = Uuid::new_v4().to_string();
+        let id = asset.get("id").and_then(|v| v.as_str()).unwrap_or(&default_id).to_string();
+
+        let now = Utc::now().to_rfc3339();
+        if let Some(obj) = asset.as_object_mut() {
+            obj.entry("id").or_insert_with(|| serde_json::Value::String(id.clone()));
+            obj.insert("updated_at".to_string(), serde_json::Value::String(now.clone()));
+            obj.entry("created_at").or_insert_with(|| serde_json::Value::String(now));
+        }
+
+        assets.insert(id.clone(), asset);
+        Ok(id)
+    }
+
+    async fn delete_asset(&self, asset_id: &str) -> Result<(), String> {
+        let mut assets = self.assets.lock().map_err(|e| format!("Asset store lock poisoned: {}", e))?;
+        assets.remove(asset_id).map(|_| ()).ok_or_else(|| "Asset not found".to_string())
+    }
+
+    async fn get_setting(&self, key: &str) -> Result<Option<String>, String> {
+        let settings = self.settings.lock().map_err(|e| format!("Settings store lock poisoned: {}", e))?;
+        Ok(settings.get(key).cloned())
+    }
+
+    async fn save_setting(&self, key: &str, value: &str) -> Result<(), String> {
+        let mut settings = self.settings.lock().map_err(|e| format!("Settings store lock poisoned: {}", e))?;
+        settings.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn seed(storage: &MemoryStorage, asset_type: &str, name: &str) {
+        storage.save_asset(serde_json::json!({ "asset_type": asset_type, "name": name }))
+            .await
+            .expect("save_asset");
+    }
+
+    #[tokio::test]
+    async fn get_assets_filters_by_substring_search_and_type() {
+        let storage = MemoryStorage::new();
+        seed(&storage, "texture", "Stone Wall").await;
+        seed(&storage, "audio", "Stone Footstep").await;
+        seed(&storage, "texture", "Wood Floor").await;
+
+        let by_search = storage.get_assets(AssetFilters {
+            search: Some("stone".to_string()),
+            ..Default::default()
+        }).await.expect("get_assets");
+        assert_eq!(by_search.len(), 2);
+
+        let by_type_and_search = storage.get_assets(AssetFilters {
+            search: Some("stone".to_string()),
+            r#type: Some("texture".to_string()),
+            ..Default::default()
+        }).await.expect("get_assets");
+        assert_eq!(by_type_and_search.len(), 1);
+        assert_eq!(by_type_and_search[0].get("name").and_then(|v| v.as_str()), Some("Stone Wall"));
+    }
+
+    #[tokio::test]
+    async fn get_assets_respects_limit() {
+        let storage = MemoryStorage::new();
+        seed(&storage, "texture", "A").await;
+        seed(&storage, "texture", "B").await;
+        seed(&storage, "texture", "C").await;
+
+        let limited = storage.get_assets(AssetFilters {
+            limit: Some(2),
+            ..Default::default()
+        }).await.expect("get_assets");
+        assert_eq!(limited.len(), 2);
+    }
+}
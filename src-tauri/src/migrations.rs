@@ -0,0 +1,136 @@
+use rusqlite::Connection;
+
+/// A single schema change, identified by the `PRAGMA user_version` it brings
+/// the database to. Steps must be listed in ascending, contiguous order.
+enum Migration {
+    Sql(&'static str),
+}
+
+struct MigrationStep {
+    version: i32,
+    migration: Migration,
+}
+
+const MIGRATIONS: &[MigrationStep] = &[
+    MigrationStep {
+        version: 1,
+        migration: Migration::Sql(
+            "CREATE TABLE IF NOT EXISTS assets (
+                id TEXT PRIMARY KEY,
+                asset_type TEXT NOT NULL,
+                name TEXT NOT NULL,
+                config TEXT,
+                metadata TEXT,
+                file_path TEXT,
+                file_size INTEGER,
+                quality_score INTEGER,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );",
+        ),
+    },
+    MigrationStep {
+        version: 2,
+        migration: Migration::Sql(
+            "CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );",
+        ),
+    },
+    MigrationStep {
+        version: 3,
+        migration: Migration::Sql(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS assets_fts USING fts5(
+                name,
+                config,
+                metadata,
+                content='assets',
+                content_rowid='rowid'
+            );
+
+            INSERT INTO assets_fts(rowid, name, config, metadata)
+                SELECT rowid, name, coalesce(config, ''), coalesce(metadata, '') FROM assets;
+
+            CREATE TRIGGER IF NOT EXISTS assets_fts_ai AFTER INSERT ON assets BEGIN
+                INSERT INTO assets_fts(rowid, name, config, metadata)
+                VALUES (new.rowid, new.name, coalesce(new.config, ''), coalesce(new.metadata, ''));
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS assets_fts_ad AFTER DELETE ON assets BEGIN
+                INSERT INTO assets_fts(assets_fts, rowid, name, config, metadata)
+                VALUES ('delete', old.rowid, old.name, coalesce(old.config, ''), coalesce(old.metadata, ''));
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS assets_fts_au AFTER UPDATE ON assets BEGIN
+                INSERT INTO assets_fts(assets_fts, rowid, name, config, metadata)
+                VALUES ('delete', old.rowid, old.name, coalesce(old.config, ''), coalesce(old.metadata, ''));
+                INSERT INTO assets_fts(rowid, name, config, metadata)
+                VALUES (new.rowid, new.name, coalesce(new.config, ''), coalesce(new.metadata, ''));
+            END;",
+        ),
+    },
+    MigrationStep {
+        version: 4,
+        migration: Migration::Sql(
+            "CREATE TABLE IF NOT EXISTS collections (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                parent_id TEXT REFERENCES collections(id),
+                created_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS asset_collections (
+                asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                collection_id TEXT NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+                PRIMARY KEY (asset_id, collection_id)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_collections_parent_id ON collections(parent_id);",
+        ),
+    },
+];
+
+/// Brings `conn` up to the latest known schema version.
+///
+/// Reads the on-disk `PRAGMA user_version`, applies every migration step
+/// whose version is greater than it (each inside its own transaction,
+/// bumping `user_version` as it commits), and errors out if the database
+/// is already newer than this binary understands - that means an older
+/// build is being pointed at a database written by a newer one.
+pub fn run_migrations(conn: &Connection) -> Result<(), String> {
+    let current_version: i32 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to read schema version: {}", e))?;
+
+    let latest_version = MIGRATIONS.iter().map(|step| step.version).max().unwrap_or(0);
+
+    if current_version > latest_version {
+        return Err(format!(
+            "Database schema version {} is newer than this build supports (latest known: {}). Please update the application.",
+            current_version, latest_version
+        ));
+    }
+
+    for step in MIGRATIONS.iter().filter(|step| step.version > current_version) {
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Failed to start migration {} transaction: {}", step.version, e))?;
+
+        match &step.migration {
+            Migration::Sql(sql) => tx
+                .execute_batch(sql)
+                .map_err(|e| format!("Migration {} failed: {}", step.version, e))?,
+        }
+
+        // PRAGMA user_version doesn't accept bound parameters.
+        tx.execute_batch(&format!("PRAGMA user_version = {}", step.version))
+            .map_err(|e| format!("Failed to record schema version {}: {}", step.version, e))?;
+
+        tx.commit()
+            .map_err(|e| format!("Failed to commit migration {}: {}", step.version, e))?;
+    }
+
+    Ok(())
+}
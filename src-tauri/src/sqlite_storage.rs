@@ -0,0 +1,383 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{params, Connection};
+use uuid::Uuid;
+
+use crate::migrations;
+use crate::storage::{AssetFilters, Storage};
+
+pub type DbPool = Pool<SqliteConnectionManager>;
+
+/// SQLite-backed `Storage` implementation, used by the running application.
+pub struct SqliteStorage {
+    pool: DbPool,
+}
+
+impl SqliteStorage {
+    /// Opens (creating if needed) the database at `db_path`, switches it to
+    /// WAL mode so readers aren't blocked by a writer, and brings it up to
+    /// the latest schema version.
+    pub fn open(db_path: &std::path::Path) -> Result<Self, String> {
+        let manager = SqliteConnectionManager::file(db_path)
+            .with_init(|conn| conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;"));
+
+        let pool = Pool::new(manager)
+            .map_err(|e| format!("Failed to create database pool: {}", e))?;
+
+        let conn = pool.get()
+            .map_err(|e| format!("Failed to check out database connection: {}", e))?;
+        migrations::run_migrations(&conn)?;
+
+        Ok(Self { pool })
+    }
+
+    /// Exposes the underlying pool for commands that need raw SQL access
+    /// this trait doesn't cover (collections).
+    pub fn pool(&self) -> DbPool {
+        self.pool.clone()
+    }
+
+    fn connection(&self) -> Result<r2d2::PooledConnection<SqliteConnectionManager>, String> {
+        self.pool.get().map_err(|e| format!("Failed to check out database connection: {}", e))
+    }
+}
+
+/// Quotes a raw user search string for FTS5's `MATCH` operand so ordinary
+/// input (`AND`/`OR`, `-`, `:`, `*`, an unbalanced `"`, ...) is treated as
+/// literal text instead of query syntax. Each whitespace-separated token
+/// becomes its own quoted phrase; FTS5 ANDs phrases together by default,
+/// so every term has to match.
+fn quote_fts_query(search: &str) -> String {
+    search
+        .split_whitespace()
+        .map(|token| format!("\"{}\"", token.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn create_asset_row_mapper() -> impl Fn(&rusqlite::Row) -> rusqlite::Result<serde_json::Value> {
+    |row| {
+        let config_str: Option<String> = row.get(3)?;
+        let metadata_str: Option<String> = row.get(4)?;
+
+        let config: Option<serde_json::Value> = config_str
+            .and_then(|s| serde_json::from_str(&s).ok());
+        let metadata: Option<serde_json::Value> = metadata_str
+            .and_then(|s| serde_json::from_str(&s).ok());
+
+        Ok(serde_json::json!({
+            "id": row.get::<_, String>(0)?,
+            "asset_type": row.get::<_, String>(1)?,
+            "name": row.get::<_, String>(2)?,
+            "config": config,
+            "metadata": metadata,
+            "file_path": row.get::<_, Option<String>>(5)?,
+            "file_size": row.get::<_, Option<u64>>(6)?,
+            "quality_score": row.get::<_, Option<u32>>(7)?,
+            "created_at": row.get::<_, String>(8)?,
+            "updated_at": row.get::<_, String>(9)?
+        }))
+    }
+}
+
+/// Upserts an asset JSON blob into the `assets` table. Updates in place on
+/// conflict rather than `INSERT OR REPLACE`, which deletes then re-inserts
+/// the row - with `foreign_keys=ON` that delete fires `ON DELETE CASCADE`
+/// on `asset_collections`, silently dropping the asset from every
+/// collection it was in. Shared by `SqliteStorage::save_asset` and
+/// `SqliteStorage::save_assets_batch` (which the import scanner uses to
+/// insert all of its rows in one transaction).
+pub(crate) fn save_asset_row(conn: &Connection, asset: &serde_json::Value) -> Result<String, String> {
+    let default_id = Uuid::new_v4().to_string();
+    let id = asset.get("id")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&default_id);
+
+    let asset_type = asset.get("asset_type")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing asset_type")?;
+
+    let name = asset.get("name")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing name")?;
+
+    let config_str = asset.get("config")
+        .map(|v| serde_json::to_string(v).unwrap_or_default());
+
+    let metadata_str = asset.get("metadata")
+        .map(|v| serde_json::to_string(v).unwrap_or_default());
+
+    let file_path = asset.get("file_path")
+        .and_then(|v| v.as_str());
+
+    let file_size = asset.get("file_size")
+        .and_then(|v| v.as_u64());
+
+    let quality_score = asset.get("quality_score")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32);
+
+    let now = Utc::now().to_rfc3339();
+    let created_at = asset.get("created_at")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&now);
+
+    conn.execute(
+        "INSERT INTO assets
+         (id, asset_type, name, config, metadata, file_path, file_size, quality_score, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+         ON CONFLICT(id) DO UPDATE SET
+             asset_type = excluded.asset_type,
+             name = excluded.name,
+             config = excluded.config,
+             metadata = excluded.metadata,
+             file_path = excluded.file_path,
+             file_size = excluded.file_size,
+             quality_score = excluded.quality_score,
+             updated_at = excluded.updated_at",
+        params![id, asset_type, name, config_str, metadata_str, file_path, file_size, quality_score, created_at, now],
+    ).map_err(|e| format!("Failed to save asset: {}", e))?;
+
+    Ok(id.to_string())
+}
+
+#[async_trait]
+impl Storage for SqliteStorage {
+    async fn get_assets(&self, filters: AssetFilters) -> Result<Vec<serde_json::Value>, String> {
+        let conn = self.connection()?;
+
+        let search = filters.search.as_deref().map(str::trim).filter(|s| !s.is_empty());
+
+        let mut bind_params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        let mut query = String::new();
+        let mut conditions = Vec::new();
+
+        if let Some(collection_id) = &filters.collection_id {
+            // Walk the collection tree down from collection_id, tracking the
+            // path of ids visited so a cycle (shouldn't exist, but data can
+            // be edited by hand) terminates the recursion instead of looping.
+            query.push_str(
+                "WITH RECURSIVE collection_tree(id, path) AS ( \
+                    SELECT id, ',' || id || ',' FROM collections WHERE id = ? \
+                    UNION ALL \
+                    SELECT c.id, t.path || c.id || ',' \
+                    FROM collections c JOIN collection_tree t ON c.parent_id = t.id \
+                    WHERE t.path NOT LIKE '%,' || c.id || ',%' \
+                ) ",
+            );
+            bind_params.push(Box::new(collection_id.clone()));
+        }
+
+        if let Some(search) = search {
+            // Full-text path: rank by BM25 so the best matches surface first.
+            query.push_str(
+                "SELECT assets.id, assets.asset_type, assets.name, assets.config, assets.metadata, \
+                 assets.file_path, assets.file_size, assets.quality_score, assets.created_at, assets.updated_at \
+                 FROM assets_fts JOIN assets ON assets.rowid = assets_fts.rowid",
+            );
+            conditions.push("assets_fts MATCH ?".to_string());
+            bind_params.push(Box::new(quote_fts_query(search)));
+
+            if let Some(asset_type) = &filters.r#type {
+                conditions.push("assets.asset_type = ?".to_string());
+                bind_params.push(Box::new(asset_type.clone()));
+            }
+
+            if filters.collection_id.is_some() {
+                conditions.push(
+                    "assets.id IN (SELECT asset_id FROM asset_collections WHERE collection_id IN (SELECT id FROM collection_tree))".to_string(),
+                );
+            }
+
+            query.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+            query.push_str(" ORDER BY bm25(assets_fts)");
+        } else {
+            // No query text: fall back to the plain listing, newest first.
+            query.push_str("SELECT id, asset_type, name, config, metadata, file_path, file_size, quality_score, created_at, updated_at FROM assets");
+
+            if let Some(asset_type) = &filters.r#type {
+                conditions.push("asset_type = ?".to_string());
+                bind_params.push(Box::new(asset_type.clone()));
+            }
+
+            if filters.collection_id.is_some() {
+                conditions.push(
+                    "id IN (SELECT asset_id FROM asset_collections WHERE collection_id IN (SELECT id FROM collection_tree))".to_string(),
+                );
+            }
+
+            if !conditions.is_empty() {
+                query.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+            }
+
+            query.push_str(" ORDER BY updated_at DESC");
+        }
+
+        if let Some(limit) = filters.limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut stmt = conn.prepare(&query)
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let param_refs: Vec<&dyn rusqlite::ToSql> = bind_params.iter().map(|p| p.as_ref()).collect();
+        let rows = stmt.query_map(param_refs.as_slice(), create_asset_row_mapper())
+            .map_err(|e| format!("Query execution error: {}", e))?;
+
+        let mut assets = Vec::new();
+        for asset_result in rows {
+            assets.push(asset_result.map_err(|e| format!("Row parsing error: {}", e))?);
+        }
+
+        Ok(assets)
+    }
+
+    async fn save_asset(&self, asset: serde_json::Value) -> Result<String, String> {
+        let conn = self.connection()?;
+        save_asset_row(&conn, &asset)
+    }
+
+    async fn save_assets_batch(&self, assets: Vec<serde_json::Value>) -> Vec<Result<String, String>> {
+        let conn = match self.connection() {
+            Ok(conn) => conn,
+            Err(e) => return assets.iter().map(|_| Err(e.clone())).collect(),
+        };
+
+        let tx = match conn.unchecked_transaction() {
+            Ok(tx) => tx,
+            Err(e) => return assets.iter()
+                .map(|_| Err(format!("Failed to start batch transaction: {}", e)))
+                .collect(),
+        };
+
+        let results: Vec<Result<String, String>> = assets.iter()
+            .map(|asset| save_asset_row(&tx, asset))
+            .collect();
+
+        if let Err(e) = tx.commit() {
+            return assets.iter()
+                .map(|_| Err(format!("Failed to commit batch transaction: {}", e)))
+                .collect();
+        }
+
+        results
+    }
+
+    async fn delete_asset(&self, asset_id: &str) -> Result<(), String> {
+        let conn = self.connection()?;
+
+        let rows_affected = conn.execute(
+            "DELETE FROM assets WHERE id = ?1",
+            params![asset_id],
+        ).map_err(|e| format!("Failed to delete asset: {}", e))?;
+
+        if rows_affected == 0 {
+            return Err("Asset not found".to_string());
+        }
+
+        Ok(())
+    }
+
+    async fn get_setting(&self, key: &str) -> Result<Option<String>, String> {
+        let conn = self.connection()?;
+
+        let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?1")
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let result = stmt.query_row(params![key], |row| row.get::<_, String>(0));
+
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Database error: {}", e)),
+        }
+    }
+
+    async fn save_setting(&self, key: &str, value: &str) -> Result<(), String> {
+        let conn = self.connection()?;
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)",
+            params![key, value, now],
+        ).map_err(|e| format!("Failed to save setting: {}", e))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_fts_query_escapes_special_characters() {
+        assert_eq!(quote_fts_query("C++"), "\"C++\"");
+        assert_eq!(quote_fts_query("foo:bar"), "\"foo:bar\"");
+        assert_eq!(quote_fts_query("\"tile"), "\"\"\"tile\"");
+        assert_eq!(quote_fts_query("AND OR"), "\"AND\" \"OR\"");
+    }
+
+    #[tokio::test]
+    async fn get_assets_search_handles_fts_special_characters() {
+        let db_path = std::env::temp_dir().join(format!("tpt_sqlite_storage_test_{}.db", Uuid::new_v4()));
+        let storage = SqliteStorage::open(&db_path).expect("open test database");
+
+        storage.save_asset(serde_json::json!({
+            "asset_type": "texture",
+            "name": "C++ tileset"
+        })).await.expect("save asset");
+
+        let results = storage.get_assets(AssetFilters {
+            search: Some("C++".to_string()),
+            ..Default::default()
+        }).await.expect("search with FTS special characters should not error");
+
+        assert_eq!(results.len(), 1);
+
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[tokio::test]
+    async fn resaving_an_asset_does_not_cascade_delete_its_collection_membership() {
+        let db_path = std::env::temp_dir().join(format!("tpt_sqlite_storage_test_{}.db", Uuid::new_v4()));
+        let storage = SqliteStorage::open(&db_path).expect("open test database");
+
+        let asset_id = storage.save_asset(serde_json::json!({
+            "asset_type": "texture",
+            "name": "Stone Wall"
+        })).await.expect("save asset");
+
+        let conn = storage.connection().expect("checkout connection");
+        conn.execute(
+            "INSERT INTO collections (id, name, parent_id, created_at) VALUES ('col-1', 'Walls', NULL, datetime('now'))",
+            [],
+        ).expect("create collection");
+        conn.execute(
+            "INSERT INTO asset_collections (asset_id, collection_id) VALUES (?1, 'col-1')",
+            params![asset_id],
+        ).expect("assign asset to collection");
+        drop(conn);
+
+        // Re-saving (an edit) should update the row in place rather than
+        // delete + re-insert it, which would cascade-delete the membership.
+        storage.save_asset(serde_json::json!({
+            "id": asset_id,
+            "asset_type": "texture",
+            "name": "Stone Wall (renamed)"
+        })).await.expect("save asset again");
+
+        let conn = storage.connection().expect("checkout connection");
+        let membership_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM asset_collections WHERE asset_id = ?1 AND collection_id = 'col-1'",
+            params![asset_id],
+            |row| row.get(0),
+        ).expect("count membership");
+
+        assert_eq!(membership_count, 1);
+
+        let _ = std::fs::remove_file(&db_path);
+    }
+}
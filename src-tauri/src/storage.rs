@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetFilters {
+    pub r#type: Option<String>,
+    /// Full-text query ranked by relevance. Empty or missing skips text
+    /// matching; the other filters still apply.
+    pub search: Option<String>,
+    pub limit: Option<u32>,
+    /// Scopes results to a collection and all of its descendants.
+    pub collection_id: Option<String>,
+}
+
+/// Persistence contract for assets and settings, kept independent of any
+/// particular backend. `SqliteStorage` is the production implementation;
+/// `MemoryStorage` backs tests so the batch worker, import scanner, and
+/// search layers can be exercised deterministically without a real
+/// database file.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn get_assets(&self, filters: AssetFilters) -> Result<Vec<serde_json::Value>, String>;
+    async fn save_asset(&self, asset: serde_json::Value) -> Result<String, String>;
+    async fn delete_asset(&self, asset_id: &str) -> Result<(), String>;
+    async fn get_setting(&self, key: &str) -> Result<Option<String>, String>;
+    async fn save_setting(&self, key: &str, value: &str) -> Result<(), String>;
+
+    /// Persists multiple assets, one result per input in the same order.
+    /// The default falls back to a `save_asset` call per item; `SqliteStorage`
+    /// overrides this to run the whole batch inside a single transaction.
+    async fn save_assets_batch(&self, assets: Vec<serde_json::Value>) -> Vec<Result<String, String>> {
+        let mut results = Vec::with_capacity(assets.len());
+        for asset in assets {
+            results.push(self.save_asset(asset).await);
+        }
+        results
+    }
+}